@@ -1,11 +1,16 @@
 //! Construct a DFA from the contents of a file.
 //!
+//! The textual description can be read through any [`io::BufRead`] with
+//! [`DFABuilder::from`], or directly from a `&str` with
+//! [`DFABuilder::from_str`]. Only the latter is available without the `std`
+//! feature, since there is no standard IO to build against in a `no_std`
+//! context.
+//!
 //! # Example
 //! ```
 //! # use dfa::DFABuilder;
-//! # use std::io;
 //! // Try to build a DFA for (ab)*
-//! let builder = DFABuilder::from(&mut io::Cursor::new(
+//! let builder = DFABuilder::from_str(
 //!     r#"
 //!     0
 //!     0 a 1
@@ -15,7 +20,7 @@
 //!     2 a 2
 //!     2 b 2
 //!     "#,
-//! ));
+//! );
 //!
 //! # assert_eq!(true, builder.is_ok());
 //! let builder = builder.unwrap().build();
@@ -27,28 +32,56 @@
 //! ```
 
 #![deny(missing_docs)]
-
-use std::collections::HashMap;
-use std::collections::HashSet;
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
+// The test suite spells membership checks as `assert_eq!(true, ..)`; keep that
+// convention rather than rewrite every assertion.
+#![cfg_attr(test, allow(clippy::bool_assert_comparison))]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::{BTreeMap as HashMap, BTreeSet as HashSet};
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(all(not(feature = "std"), test))]
+use alloc::string::String;
+
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(any(feature = "std", test))]
 use std::io;
 
-mod state;
+use core::ops::RangeInclusive;
+
+mod regex;
+pub mod state;
 use state::State;
 
 /// DFA represents a Deterministic Finite Automata.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DFA {
     /// A vector of all the states that are considered a final state.
     final_states: Vec<i32>,
 
     /// A vector of all of the states for this DFA.
     states: HashMap<i32, State>,
+
+    /// The token tag associated with each tagged final state, if any.
+    ///
+    /// Tags are an optional scanning concern and aren't part of the language
+    /// itself, so they're left out of the serialized form.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    final_state_tags: HashMap<i32, i32>,
 }
 
 impl DFA {
     /// Return `true` if `s` is a valid string in the language represented
     /// by this `DFA`.
-    pub fn is_valid_string<'a>(&self, s: &'a str) -> bool {
+    pub fn is_valid_string(&self, s: &str) -> bool {
         // Check trivial case of the empty string.
         if s.is_empty() {
             self.final_states.contains(&0)
@@ -56,7 +89,7 @@ impl DFA {
             let mut current_state = (&0, self.get_state(&0));
 
             for c in s.chars() {
-                let next_state = current_state.1.transition_for(&c);
+                let next_state = current_state.1.transition_for(c);
 
                 match next_state {
                     Some(next_state) => current_state = (next_state, self.get_state(next_state)),
@@ -68,6 +101,240 @@ impl DFA {
         }
     }
 
+    /// Collapse equivalent states into the unique minimal DFA accepting the
+    /// same language using Hopcroft's partition-refinement algorithm.
+    ///
+    /// The transition function is assumed to be complete (as guaranteed by
+    /// [`DFABuilder::build`]); any dead/sink state is preserved so it stays
+    /// total. The block containing the original start state (id `0`) becomes
+    /// the new start state.
+    pub fn minimize(self) -> DFA {
+        // One representative per atomic interval is enough to refine the
+        // partition, since characters in the same interval are indistinguishable.
+        let alphabet: HashSet<char> = alphabet_representatives(&self.states);
+
+        // Only states reachable from the start can affect the language;
+        // unreachable ones must not survive into the minimal automaton.
+        let mut all: HashSet<i32> = HashSet::new();
+        let mut stack = vec![0];
+        while let Some(state) = stack.pop() {
+            if all.insert(state) {
+                for (_, target) in self.get_state(&state).transitions() {
+                    stack.push(*target);
+                }
+            }
+        }
+
+        let finals: HashSet<i32> = self
+            .final_states
+            .iter()
+            .copied()
+            .filter(|s| all.contains(s))
+            .collect();
+        let non_finals: HashSet<i32> = all.difference(&finals).copied().collect();
+
+        // Start from the partition {non-final states} together with one block
+        // per distinct token tag among the final states (untagged finals share
+        // the `None` block). Splitting finals by tag keeps states that would
+        // report different tokens from ever being merged.
+        let mut final_groups: HashMap<Option<i32>, HashSet<i32>> = HashMap::new();
+        for &state in &finals {
+            final_groups
+                .entry(self.final_state_tags.get(&state).copied())
+                .or_default()
+                .insert(state);
+        }
+
+        let mut partition: Vec<HashSet<i32>> = final_groups
+            .into_values()
+            .chain(core::iter::once(non_finals))
+            .filter(|block| !block.is_empty())
+            .collect();
+        let mut worklist: Vec<HashSet<i32>> = partition.clone();
+
+        while let Some(a) = worklist.pop() {
+            for &c in &alphabet {
+                // `x` is the set of states whose `c`-transition lands in `a`.
+                let x: HashSet<i32> = all
+                    .iter()
+                    .copied()
+                    .filter(|state| {
+                        self.get_state(state)
+                            .transition_for(c)
+                            .is_some_and(|target| a.contains(target))
+                    })
+                    .collect();
+
+                if x.is_empty() {
+                    continue;
+                }
+
+                let mut refined = Vec::with_capacity(partition.len());
+                for y in partition.drain(..) {
+                    let intersection: HashSet<i32> = y.intersection(&x).copied().collect();
+                    let difference: HashSet<i32> = y.difference(&x).copied().collect();
+
+                    // `x` only splits `y` if both pieces are non-empty.
+                    if intersection.is_empty() || difference.is_empty() {
+                        refined.push(y);
+                        continue;
+                    }
+
+                    // If `y` is still on the worklist, both pieces must be;
+                    // otherwise the smaller piece suffices (the standard rule).
+                    if let Some(pos) = worklist.iter().position(|block| *block == y) {
+                        worklist.remove(pos);
+                        worklist.push(intersection.clone());
+                        worklist.push(difference.clone());
+                    } else if intersection.len() <= difference.len() {
+                        worklist.push(intersection.clone());
+                    } else {
+                        worklist.push(difference.clone());
+                    }
+
+                    refined.push(intersection);
+                    refined.push(difference);
+                }
+                partition = refined;
+            }
+        }
+
+        // Map each original state to the index of its block.
+        let mut block_of: HashMap<i32, usize> = HashMap::new();
+        for (index, block) in partition.iter().enumerate() {
+            for &state in block {
+                block_of.insert(state, index);
+            }
+        }
+
+        // The block holding the original start state becomes the new start, so
+        // it is remapped to id 0 and every other block follows after it.
+        let start_block = block_of[&0];
+        let new_id = |block: usize| -> i32 {
+            if block == start_block {
+                0
+            } else if block < start_block {
+                block as i32 + 1
+            } else {
+                block as i32
+            }
+        };
+
+        // Carry any final-state tags over to the block that now represents the
+        // original state.
+        let mut final_state_tags = HashMap::new();
+        for (state, tag) in &self.final_state_tags {
+            if let Some(&block) = block_of.get(state) {
+                final_state_tags.entry(new_id(block)).or_insert(*tag);
+            }
+        }
+
+        let mut states = HashMap::new();
+        let mut final_states = Vec::new();
+        for (index, block) in partition.iter().enumerate() {
+            // Every state in a block behaves identically, so a representative
+            // stands in for the whole block.
+            let representative = *block.iter().next().unwrap();
+            let id = new_id(index);
+
+            let mut state = State::default();
+            for (range, target) in self.get_state(&representative).transitions() {
+                state.add_range_transition(range.clone(), new_id(block_of[target]));
+            }
+            states.insert(id, state);
+
+            if block.iter().any(|s| self.final_states.contains(s)) {
+                final_states.push(id);
+            }
+        }
+
+        DFA {
+            states,
+            final_states,
+            final_state_tags,
+        }
+    }
+
+    /// Return the byte length of the longest prefix of `input` accepted by this
+    /// `DFA`, or `None` if no prefix (not even the empty one) is accepted.
+    pub fn longest_match(&self, input: &str) -> Option<usize> {
+        self.scan(input).map(|(len, _tag)| len)
+    }
+
+    /// Like [`longest_match`](DFA::longest_match), but also reports the token
+    /// tag of the final state where the match ended (if that state was tagged
+    /// via [`DFABuilder::add_final_state_with_tag`]).
+    pub fn longest_match_tagged(&self, input: &str) -> Option<(usize, Option<i32>)> {
+        self.scan(input)
+    }
+
+    /// Scan `input` as a sequence of tokens using maximal munch, returning the
+    /// `(start, end)` byte range of each token.
+    ///
+    /// Scanning stops at the first position where no non-empty prefix is
+    /// accepted, so any trailing unmatched input is simply not returned.
+    pub fn tokenize(&self, input: &str) -> Vec<(usize, usize)> {
+        self.tokenize_tagged(input)
+            .into_iter()
+            .map(|(start, end, _tag)| (start, end))
+            .collect()
+    }
+
+    /// Like [`tokenize`](DFA::tokenize), but also reports each token's tag.
+    pub fn tokenize_tagged(&self, input: &str) -> Vec<(usize, usize, Option<i32>)> {
+        let mut tokens = Vec::new();
+        let mut start = 0;
+
+        while start < input.len() {
+            match self.scan(&input[start..]) {
+                // A zero-width match can't advance the cursor, so treat it like
+                // no match and stop rather than looping forever.
+                Some((len, tag)) if len > 0 => {
+                    tokens.push((start, start + len, tag));
+                    start += len;
+                }
+                _ => break,
+            }
+        }
+
+        tokens
+    }
+
+    /// Walk `input` one character at a time, remembering the position of the
+    /// last visit to a final state. On a dead transition we back up to that
+    /// remembered position rather than failing outright.
+    ///
+    /// Returns the `(byte length, tag)` of the longest accepted prefix.
+    fn scan(&self, input: &str) -> Option<(usize, Option<i32>)> {
+        let mut current = &0;
+        let mut last_accept = None;
+
+        // The empty prefix is accepted when the start state is itself final.
+        if self.final_states.contains(current) {
+            last_accept = Some((0, self.tag_for(current)));
+        }
+
+        for (i, c) in input.char_indices() {
+            match self.get_state(current).transition_for(c) {
+                Some(next) => {
+                    current = next;
+                    if self.final_states.contains(current) {
+                        last_accept = Some((i + c.len_utf8(), self.tag_for(current)));
+                    }
+                }
+                // Dead transition: fall back to the last final state seen.
+                None => break,
+            }
+        }
+
+        last_accept
+    }
+
+    /// Return the token tag associated with `state_id`, if it was tagged.
+    fn tag_for(&self, state_id: &i32) -> Option<i32> {
+        self.final_state_tags.get(state_id).copied()
+    }
+
     /// Return the `State` with the specified `state_id`.
     fn get_state(&self, state_id: &i32) -> &State {
         // We can unwrap here safely because it's ensured in
@@ -78,6 +345,7 @@ impl DFA {
 }
 
 #[derive(Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 /// `DFABuilder` is used to create a DFA with validation.
 pub struct DFABuilder {
     /// A vector of all the states that are considered a final state.
@@ -87,7 +355,15 @@ pub struct DFABuilder {
     states: HashMap<i32, State>,
 
     /// The vector for the alphabet the `DFA` will operate under.
+    ///
+    /// This is fully determined by `states`, so it is recomputed rather than
+    /// persisted in the serialized form.
+    #[cfg_attr(feature = "serde", serde(skip))]
     alphabet: HashSet<char>,
+
+    /// The token tag associated with each tagged final state, if any.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    final_state_tags: HashMap<i32, i32>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -103,30 +379,53 @@ pub enum DFABuilderError {
     ExpectedChar,
     /// Represents when the DFA expected an integer but was given something else.
     ExptectedInt,
+    /// Represents a regular expression that couldn't be compiled into a DFA.
+    InvalidRegex(&'static str),
 }
 
-impl From<std::num::ParseIntError> for DFABuilderError {
-    fn from(_err: std::num::ParseIntError) -> DFABuilderError {
+impl From<core::num::ParseIntError> for DFABuilderError {
+    fn from(_err: core::num::ParseIntError) -> DFABuilderError {
         DFABuilderError::ExptectedInt
     }
 }
 
 impl DFABuilder {
-    /// Create a builder from the specified BufRead.
-    pub fn from<T: io::BufRead>(read: T) -> Result<Self, DFABuilderError> {
+    /// Create a builder from the specified `BufRead`.
+    #[cfg(any(feature = "std", test))]
+    pub fn from<T: io::BufRead>(mut read: T) -> Result<Self, DFABuilderError> {
+        // Slurp the whole stream so the actual parsing can happen over a
+        // string, keeping it independent of the IO trait in use.
+        let mut contents = String::new();
+        if read.read_to_string(&mut contents).is_err() {
+            return Err(DFABuilderError::MalformedLine("Unable to read input"));
+        }
+
+        DFABuilder::from_str(&contents)
+    }
+
+    /// Create a builder directly from the textual DFA description in `contents`,
+    /// without going through any IO trait.
+    ///
+    /// Lines are split on `\n`; leading/trailing whitespace and blank lines are
+    /// ignored. The first non-empty line lists the final states, and every
+    /// subsequent line is a `from_state letter to_state` transition.
+    // An inherent `from_str` (rather than a `FromStr` impl) keeps it callable
+    // without importing the trait, mirroring the existing `from` constructor.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(contents: &str) -> Result<Self, DFABuilderError> {
         let mut builder = DFABuilder::default();
-        let lines: Vec<String> = read.lines()
-            .map(|r| r.unwrap())
-            .map(|s| s.trim().to_owned())
+        let lines: Vec<&str> = contents
+            .lines()
+            .map(|s| s.trim())
             .filter(|s| !s.is_empty())
             .collect();
 
         // If the content we're reading from is empty, return an error.
-        if lines.len() == 0 {
+        if lines.is_empty() {
             return Err(DFABuilderError::EmptyStream);
         }
 
-        let mut lines = lines.iter();
+        let mut lines = lines.into_iter();
 
         // We can safely unwrap this value because at this point, the length of
         // lines is guaranteed to be at least 1.
@@ -145,27 +444,35 @@ impl DFABuilder {
 
         // Proceed through the rest of the lines in the data.
         for line in lines {
-            // Attempt to parse the data in the line to (from_state, w, to_state),
+            // Attempt to parse the data in the line to (from_state, range, to_state),
             // if this was not possible, error will be returned (because of ? syntax).
-            let (from_state, w, to_state) = parse_line(&line)?;
-
-            // We can just insert the character here because by the definition
-            // of a set, it cannot have duplicates.
-            builder.alphabet.insert(w);
+            let (from_state, range, to_state) = parse_line(line)?;
 
             // Get, or insert, the `from_state`.
-            let mut state = builder
+            let state = builder
                 .states
                 .entry(from_state)
                 .or_insert_with(State::default);
 
-            // Add the transition to the state.
-            state.add_transition(w, to_state);
+            // Add the transition to the state. The alphabet is derived from the
+            // interval set in `build`, so it isn't accumulated here.
+            state.add_range_transition(range, to_state);
         }
 
         Ok(builder)
     }
 
+    /// Create a builder from the regular expression `pattern`.
+    ///
+    /// The pattern supports literals, concatenation, `|` alternation, the `*`,
+    /// `+` and `?` quantifiers, and parenthesized groups. It is compiled into
+    /// an NFA with Thompson construction and then determinised with subset
+    /// construction, producing a complete transition function that plugs
+    /// straight into [`build`](DFABuilder::build).
+    pub fn from_regex(pattern: &str) -> Result<Self, DFABuilderError> {
+        regex::build_from_regex(pattern)
+    }
+
     /// Add the specified state id as a final state.
     ///
     /// `state_id` the ID of the state that is considered a final state.
@@ -173,56 +480,108 @@ impl DFABuilder {
         self.final_states.push(state_id);
     }
 
+    /// Add the specified state id as a final state, associating a user-supplied
+    /// token `tag` with it.
+    ///
+    /// The tag is reported by the scanning API (see [`DFA::longest_match_tagged`])
+    /// so lexers can tell which rule matched. If the same state is tagged more
+    /// than once, the first tag registered wins.
+    pub fn add_final_state_with_tag(&mut self, state_id: i32, tag: i32) {
+        self.add_final_state(state_id);
+        self.final_state_tags.entry(state_id).or_insert(tag);
+    }
+
     /// Return the states loaded for this DFA.
     pub fn states(&self) -> &HashMap<i32, State> {
         &self.states
     }
 
+    /// Recompute the alphabet as one representative character per atomic
+    /// interval of every state's transitions.
+    ///
+    /// Used after constructing a builder from a source that doesn't carry the
+    /// alphabet explicitly, such as deserialization.
+    #[cfg(feature = "serde")]
+    fn rebuild_alphabet(&mut self) {
+        self.alphabet = alphabet_representatives(&self.states);
+    }
+
     /// Attempt to build the `DFA` specified in this `DFABuilder`.
-    pub fn build(self) -> Option<DFA> {
+    pub fn build(mut self) -> Option<DFA> {
         // Ensure there's an initial state.
-        if self.states.get(&0).is_none() {
-            return None;
-        }
+        self.states.get(&0)?;
 
         // Ensure there's at least one final state.
-        if self.final_states.len() < 1 {
+        if self.final_states.is_empty() {
             return None;
         }
 
-        // Ensure all states have a branch for each item in the alphabet.
-        for (_state_id, state) in &self.states {
-            for w in &self.alphabet {
-                // Get the transition for the current item in the alphabet.
-                let transition = state.transition_for(w);
+        // The alphabet is derived from the transition intervals: one
+        // representative character per atomic interval, so coverage of the
+        // whole alphabet can be checked without enumerating every codepoint.
+        self.alphabet = alphabet_representatives(&self.states);
 
-                // If the transition doesn't exist, we can't successfully return a DFA.
-                if transition.is_none() {
-                    return None;
-                }
-
-                // At this point, there's guaranteed to be a transition for the item,
-                // so we can safely unwrap it.
-                let transition = transition.unwrap();
+        // Ensure every state's intervals cover each alphabet symbol.
+        for state in self.states.values() {
+            for &w in &self.alphabet {
+                // Get the transition for the current item in the alphabet; if it
+                // doesn't exist, we can't successfully return a DFA.
+                let transition = state.transition_for(w)?;
 
                 // Ensure that the state being transitioned to actually exists.
-                if self.states.get(transition).is_none() {
-                    return None;
-                }
+                self.states.get(transition)?;
             }
         }
 
         Some(DFA {
             states: self.states,
             final_states: self.final_states,
+            final_state_tags: self.final_state_tags,
         })
     }
 }
 
+/// Compute one representative character per atomic interval across every
+/// state's transitions.
+///
+/// The transition intervals partition into atomic pieces at every interval
+/// boundary; within a piece no interval starts or ends, so a single member
+/// stands in for the whole piece when checking alphabet coverage or driving
+/// partition refinement.
+fn alphabet_representatives(states: &HashMap<i32, State>) -> HashSet<char> {
+    let ranges: Vec<(u32, u32)> = states
+        .values()
+        .flat_map(|state| state.transitions())
+        .map(|(range, _)| (*range.start() as u32, *range.end() as u32))
+        .collect();
+
+    // Boundaries fall at each interval start and just past each interval end.
+    let mut breakpoints: Vec<u32> = Vec::with_capacity(ranges.len() * 2);
+    for &(lo, hi) in &ranges {
+        breakpoints.push(lo);
+        breakpoints.push(hi + 1);
+    }
+    breakpoints.sort_unstable();
+    breakpoints.dedup();
+
+    let mut representatives = HashSet::new();
+    for point in breakpoints {
+        // Keep a breakpoint only if it actually lands inside some interval (and
+        // is a valid codepoint); that makes it the start of an atomic piece.
+        if ranges.iter().any(|&(lo, hi)| lo <= point && point <= hi) {
+            if let Some(c) = char::from_u32(point) {
+                representatives.insert(c);
+            }
+        }
+    }
+
+    representatives
+}
+
 /// Parse the specified `line`.
 ///
-/// Returns a tuple of the form: `(from_state_id, transition_letter, to_state_id)`
-fn parse_line(line: &str) -> Result<(i32, char, i32), DFABuilderError> {
+/// Returns a tuple of the form: `(from_state_id, transition_interval, to_state_id)`
+fn parse_line(line: &str) -> Result<(i32, RangeInclusive<char>, i32), DFABuilderError> {
     let components: Vec<_> = line.split(' ').filter(|x| !x.trim().is_empty()).collect();
 
     if components.len() != 3 {
@@ -233,12 +592,75 @@ fn parse_line(line: &str) -> Result<(i32, char, i32), DFABuilderError> {
     } else {
         // Parse the state this transition is for.
         let from_state = components[0].parse::<i32>()?;
-        let w = components[1];
-        if w.len() > 1 {
-            return Err(DFABuilderError::ExpectedChar);
-        }
+        let range = parse_symbol(components[1])?;
         let to_state = components[2].parse::<i32>()?;
-        Ok((from_state, w.chars().next().unwrap(), to_state))
+        Ok((from_state, range, to_state))
+    }
+}
+
+/// Parse a transition symbol into a character interval.
+///
+/// Accepts either a single character (`a`) or a bracketed class (`[a-z]`,
+/// `[0-9]`); the latter keeps automata over large codepoint ranges compact.
+fn parse_symbol(symbol: &str) -> Result<RangeInclusive<char>, DFABuilderError> {
+    let chars: Vec<char> = symbol.chars().collect();
+
+    match chars.as_slice() {
+        [c] => Ok(*c..=*c),
+        ['[', lo, '-', hi, ']'] if lo <= hi => Ok(*lo..=*hi),
+        _ => Err(DFABuilderError::ExpectedChar),
+    }
+}
+
+/// The intermediate shape shared by the `DFA`/`DFABuilder` serialized forms:
+/// `{ "final_states": [..], "states": { "0": { "a": 1, .. }, .. } }`.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerdeRepr {
+    final_states: Vec<i32>,
+    states: HashMap<i32, State>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DFABuilder {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let repr = SerdeRepr::deserialize(deserializer)?;
+
+        // A builder may legitimately be incomplete, so only the alphabet is
+        // rebuilt here; validation happens in `build`.
+        let mut builder = DFABuilder {
+            final_states: repr.final_states,
+            states: repr.states,
+            alphabet: HashSet::new(),
+            final_state_tags: HashMap::new(),
+        };
+        builder.rebuild_alphabet();
+
+        Ok(builder)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DFA {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // Rebuild a builder from the serialized shape, then run the same
+        // completeness/known-state validation `build` performs, surfacing a
+        // failure as a serde error rather than a silent `None`.
+        let builder = DFABuilder::deserialize(deserializer)?;
+
+        builder.build().ok_or_else(|| {
+            serde::de::Error::custom(
+                "DFA is incomplete: a state is missing a transition for some \
+                 alphabet symbol, references an unknown state, or there is no \
+                 start/final state",
+            )
+        })
     }
 }
 
@@ -445,4 +867,234 @@ mod tests {
         assert_eq!(false, dfa.is_valid_string("aaa"));
         assert_eq!(false, dfa.is_valid_string(""));
     }
+
+    #[test]
+    fn dfa_builder_from_str_needs_no_io() {
+        // DFA for (ab)*
+        let builder = DFABuilder::from_str(
+            r#"
+        0
+        0 a 1
+        0 b 2
+        1 a 2
+        1 b 0
+        2 a 2
+        2 b 2
+        "#,
+        );
+
+        assert_eq!(true, builder.is_ok());
+        let dfa = builder.unwrap().build().unwrap();
+        assert_eq!(true, dfa.is_valid_string("abab"));
+        assert_eq!(false, dfa.is_valid_string("aba"));
+    }
+
+    #[test]
+    fn regex_builds_dfa_for_alternation_and_star() {
+        // Regex for (ab)*
+        let dfa = DFABuilder::from_regex("(ab)*").unwrap().build().unwrap();
+
+        assert_eq!(true, dfa.is_valid_string(""));
+        assert_eq!(true, dfa.is_valid_string("ab"));
+        assert_eq!(true, dfa.is_valid_string("abab"));
+        assert_eq!(false, dfa.is_valid_string("aba"));
+        assert_eq!(false, dfa.is_valid_string("ba"));
+    }
+
+    #[test]
+    fn regex_handles_quantifiers_and_groups() {
+        // Regex for a(b|c)+d?
+        let dfa = DFABuilder::from_regex("a(b|c)+d?").unwrap().build().unwrap();
+
+        assert_eq!(true, dfa.is_valid_string("ab"));
+        assert_eq!(true, dfa.is_valid_string("abc"));
+        assert_eq!(true, dfa.is_valid_string("acccbd"));
+        assert_eq!(false, dfa.is_valid_string("a"));
+        assert_eq!(false, dfa.is_valid_string("abdd"));
+    }
+
+    #[test]
+    fn regex_empty_pattern_matches_only_empty_string() {
+        let dfa = DFABuilder::from_regex("").unwrap().build().unwrap();
+
+        assert_eq!(true, dfa.is_valid_string(""));
+        assert_eq!(false, dfa.is_valid_string("a"));
+    }
+
+    #[test]
+    fn regex_builds_dfa_for_character_class() {
+        // Regex for [a-z]+ followed by a digit.
+        let dfa = DFABuilder::from_regex("[a-z]+[0-9]").unwrap().build().unwrap();
+
+        assert_eq!(true, dfa.is_valid_string("a0"));
+        assert_eq!(true, dfa.is_valid_string("hello9"));
+        assert_eq!(false, dfa.is_valid_string("0"));
+        assert_eq!(false, dfa.is_valid_string("abc"));
+        assert_eq!(false, dfa.is_valid_string("A0"));
+        // The class is a range, not the literal bracket string.
+        assert_eq!(false, dfa.is_valid_string("[a-z]"));
+    }
+
+    #[test]
+    fn minimize_preserves_language_with_redundant_states() {
+        // Three-state cycle over {a} that accepts a*; the minimal DFA is a
+        // single accepting state.
+        let builder = DFABuilder::from(&mut io::Cursor::new(
+            r#"
+            0 1 2
+            0 a 1
+            1 a 2
+            2 a 0
+            "#,
+        ));
+
+        let dfa = builder.unwrap().build().unwrap().minimize();
+
+        assert_eq!(true, dfa.is_valid_string(""));
+        assert_eq!(true, dfa.is_valid_string("a"));
+        assert_eq!(true, dfa.is_valid_string("aaaa"));
+    }
+
+    #[test]
+    fn minimize_preserves_regex_language() {
+        let dfa = DFABuilder::from_regex("(ab)*")
+            .unwrap()
+            .build()
+            .unwrap()
+            .minimize();
+
+        assert_eq!(true, dfa.is_valid_string(""));
+        assert_eq!(true, dfa.is_valid_string("abab"));
+        assert_eq!(false, dfa.is_valid_string("aba"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn dfa_round_trips_through_json() {
+        let dfa = DFABuilder::from_regex("(ab)*").unwrap().build().unwrap();
+
+        let json = serde_json::to_string(&dfa).unwrap();
+        let restored: DFA = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(true, restored.is_valid_string("abab"));
+        assert_eq!(false, restored.is_valid_string("aba"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializing_invalid_dfa_is_a_serde_error() {
+        // State 0 transitions to state 5, which doesn't exist, so the same
+        // validation `build` performs must reject it.
+        let json = r#"{ "final_states": [0], "states": { "0": { "a": 5 } } }"#;
+        let restored: Result<DFA, _> = serde_json::from_str(json);
+
+        assert_eq!(true, restored.is_err());
+    }
+
+    #[test]
+    fn longest_match_backs_up_to_last_final_state() {
+        // Regex for a+
+        let dfa = DFABuilder::from_regex("a+").unwrap().build().unwrap();
+
+        assert_eq!(Some(3), dfa.longest_match("aaab"));
+        assert_eq!(Some(4), dfa.longest_match("aaaa"));
+        assert_eq!(None, dfa.longest_match("baaa"));
+    }
+
+    #[test]
+    fn tokenize_consumes_longest_prefixes() {
+        // Regex for a single 'a'.
+        let dfa = DFABuilder::from_regex("a").unwrap().build().unwrap();
+
+        assert_eq!(vec![(0, 1), (1, 2), (2, 3)], dfa.tokenize("aaa"));
+        // A trailing non-'a' simply ends the scan.
+        assert_eq!(vec![(0, 1)], dfa.tokenize("ab"));
+    }
+
+    #[test]
+    fn minimize_keeps_differently_tagged_finals_apart() {
+        // States 1 and 2 are behaviorally identical, so they would collapse
+        // during minimization — but they carry distinct tags and must survive
+        // as separate states.
+        let mut builder = DFABuilder::from_str(
+            r#"
+            1 2
+            0 a 1
+            0 b 2
+            1 a 1
+            1 b 1
+            2 a 2
+            2 b 2
+            "#,
+        )
+        .unwrap();
+        builder.add_final_state_with_tag(1, 7);
+        builder.add_final_state_with_tag(2, 8);
+
+        let dfa = builder.build().unwrap().minimize();
+
+        assert_eq!(Some((1, Some(7))), dfa.longest_match_tagged("a"));
+        assert_eq!(Some((1, Some(8))), dfa.longest_match_tagged("b"));
+    }
+
+    #[test]
+    fn scanning_reports_the_winning_tag() {
+        // Accepts one or more 'a's; tag the accepting state with token id 7.
+        let mut builder = DFABuilder::from_str(
+            r#"
+            1
+            0 a 1
+            1 a 1
+            "#,
+        )
+        .unwrap();
+        builder.add_final_state_with_tag(1, 7);
+
+        let dfa = builder.build().unwrap();
+
+        assert_eq!(Some((3, Some(7))), dfa.longest_match_tagged("aaa"));
+        assert_eq!(vec![(0, 3, Some(7))], dfa.tokenize_tagged("aaa"));
+    }
+
+    #[test]
+    fn character_class_transitions_match_whole_range() {
+        // Lowercase identifiers: one or more letters in a..=z.
+        let builder = DFABuilder::from_str(
+            r#"
+            1
+            0 [a-z] 1
+            1 [a-z] 1
+            "#,
+        );
+
+        let dfa = builder.unwrap().build().unwrap();
+
+        assert_eq!(true, dfa.is_valid_string("hello"));
+        assert_eq!(false, dfa.is_valid_string("hello1"));
+        assert_eq!(false, dfa.is_valid_string(""));
+    }
+
+    #[test]
+    fn incomplete_interval_coverage_fails_to_build() {
+        // State 1 only handles digits, so it doesn't cover the letters that
+        // appear elsewhere in the alphabet and the build must fail.
+        let builder = DFABuilder::from_str(
+            r#"
+            1
+            0 [a-z] 1
+            1 [0-9] 1
+            "#,
+        );
+
+        assert_eq!(true, builder.is_ok());
+        assert_eq!(true, builder.unwrap().build().is_none());
+    }
+
+    #[test]
+    fn regex_rejects_unclosed_group() {
+        assert_eq!(
+            Err(DFABuilderError::InvalidRegex("Unclosed group")),
+            DFABuilder::from_regex("(ab")
+        );
+    }
 }
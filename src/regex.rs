@@ -0,0 +1,403 @@
+//! Compile a regular expression into an NFA via Thompson construction and
+//! then into a [`DFABuilder`](crate::DFABuilder) via subset construction.
+//!
+//! The pipeline mirrors the one used by lexer generators: a pattern is first
+//! parsed into a non-deterministic automaton whose sub-expressions are wired
+//! together with ε-transitions, and that automaton is then determinised so the
+//! result slots straight into the existing `DFA`/`State` structures.
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::{BTreeMap as HashMap, BTreeSet};
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::{BTreeSet, HashMap};
+
+use core::ops::RangeInclusive;
+
+use crate::state::State;
+use crate::DFABuilder;
+use crate::DFABuilderError;
+
+/// A single NFA edge. `None` represents an ε-transition; `Some(range)` a
+/// transition consuming any character in the (inclusive) interval, so a literal
+/// is just the degenerate `c..=c` range and a class like `[a-z]` a wider one.
+type Edge = (Option<RangeInclusive<char>>, usize);
+
+/// A Thompson NFA built out of numbered states and their outgoing edges.
+struct Nfa {
+    /// Outgoing edges for each state, indexed by state id.
+    states: Vec<Vec<Edge>>,
+}
+
+/// A partially constructed piece of the NFA with a single start and accept
+/// state, as produced by each sub-expression during Thompson construction.
+struct Fragment {
+    start: usize,
+    accept: usize,
+}
+
+impl Nfa {
+    /// Compile `pattern` into an NFA, returning the fragment spanning the whole
+    /// expression.
+    fn compile(pattern: &str) -> Result<(Nfa, Fragment), DFABuilderError> {
+        let mut parser = Parser {
+            input: pattern.chars().collect(),
+            pos: 0,
+            nfa: Nfa { states: Vec::new() },
+        };
+
+        let fragment = parser.parse_alternation()?;
+
+        // If any input is left over, the parse stopped at a `)` that closes no
+        // group.
+        if parser.pos != parser.input.len() {
+            return Err(DFABuilderError::InvalidRegex("Unexpected trailing input"));
+        }
+
+        Ok((parser.nfa, fragment))
+    }
+
+    /// Allocate a fresh state with no outgoing edges and return its id.
+    fn add_state(&mut self) -> usize {
+        self.states.push(Vec::new());
+        self.states.len() - 1
+    }
+
+    /// Return the set of states reachable from `states` using only
+    /// ε-transitions, including the states themselves.
+    fn epsilon_closure(&self, states: &BTreeSet<usize>) -> BTreeSet<usize> {
+        let mut closure = states.clone();
+        let mut stack: Vec<usize> = states.iter().copied().collect();
+
+        while let Some(state) = stack.pop() {
+            for (symbol, target) in &self.states[state] {
+                if symbol.is_none() && closure.insert(*target) {
+                    stack.push(*target);
+                }
+            }
+        }
+
+        closure
+    }
+}
+
+/// A recursive-descent parser that emits NFA fragments as it walks the pattern.
+struct Parser {
+    /// The pattern, pre-split into characters for positional indexing.
+    input: Vec<char>,
+    /// The index of the next unconsumed character.
+    pos: usize,
+    /// The NFA being constructed.
+    nfa: Nfa,
+}
+
+impl Parser {
+    /// Peek at the next character without consuming it.
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.pos).copied()
+    }
+
+    /// `alternation := concatenation ('|' concatenation)*`
+    fn parse_alternation(&mut self) -> Result<Fragment, DFABuilderError> {
+        let mut left = self.parse_concatenation()?;
+
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            let right = self.parse_concatenation()?;
+
+            // A new start ε-branches into both fragments and both accepts
+            // ε-merge into a new accept.
+            let start = self.nfa.add_state();
+            let accept = self.nfa.add_state();
+
+            self.nfa.states[start].push((None, left.start));
+            self.nfa.states[start].push((None, right.start));
+            self.nfa.states[left.accept].push((None, accept));
+            self.nfa.states[right.accept].push((None, accept));
+
+            left = Fragment { start, accept };
+        }
+
+        Ok(left)
+    }
+
+    /// `concatenation := repetition*`
+    fn parse_concatenation(&mut self) -> Result<Fragment, DFABuilderError> {
+        // Concatenation stops at an alternation bar, a closing paren, or the
+        // end of input. An empty concatenation is the ε-fragment.
+        let mut fragment: Option<Fragment> = None;
+
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+
+            let next = self.parse_repetition()?;
+
+            fragment = Some(match fragment {
+                None => next,
+                Some(prev) => {
+                    self.nfa.states[prev.accept].push((None, next.start));
+                    Fragment {
+                        start: prev.start,
+                        accept: next.accept,
+                    }
+                }
+            });
+        }
+
+        Ok(fragment.unwrap_or_else(|| self.empty_fragment()))
+    }
+
+    /// `repetition := atom ('*' | '+' | '?')*`
+    fn parse_repetition(&mut self) -> Result<Fragment, DFABuilderError> {
+        let mut fragment = self.parse_atom()?;
+
+        while let Some(c) = self.peek() {
+            fragment = match c {
+                '*' => self.star(fragment),
+                '+' => self.plus(fragment),
+                '?' => self.optional(fragment),
+                _ => break,
+            };
+            self.pos += 1;
+        }
+
+        Ok(fragment)
+    }
+
+    /// `atom := '(' alternation ')' | class | literal`
+    fn parse_atom(&mut self) -> Result<Fragment, DFABuilderError> {
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let fragment = self.parse_alternation()?;
+                if self.peek() != Some(')') {
+                    return Err(DFABuilderError::InvalidRegex("Unclosed group"));
+                }
+                self.pos += 1;
+                Ok(fragment)
+            }
+            Some('[') => self.parse_class(),
+            Some(c) if !is_metacharacter(c) => {
+                self.pos += 1;
+                Ok(self.interval(c..=c))
+            }
+            _ => Err(DFABuilderError::InvalidRegex("Expected an atom")),
+        }
+    }
+
+    /// `class := '[' char '-' char ']'`
+    ///
+    /// Mirrors the bracketed class accepted by the file format, matching any
+    /// single character in the inclusive range.
+    fn parse_class(&mut self) -> Result<Fragment, DFABuilderError> {
+        // input[pos] is the '[' that got us here; the body follows it.
+        match self.input.get(self.pos + 1..self.pos + 5) {
+            Some([lo, '-', hi, ']']) if lo <= hi => {
+                let range = *lo..=*hi;
+                self.pos += 5;
+                Ok(self.interval(range))
+            }
+            _ => Err(DFABuilderError::InvalidRegex("Malformed character class")),
+        }
+    }
+
+    /// Build a `start --range--> accept` fragment consuming any character in
+    /// `range` (a single literal being the degenerate one-character range).
+    fn interval(&mut self, range: RangeInclusive<char>) -> Fragment {
+        let start = self.nfa.add_state();
+        let accept = self.nfa.add_state();
+        self.nfa.states[start].push((Some(range), accept));
+        Fragment { start, accept }
+    }
+
+    /// Build an ε-only fragment, used for an empty (sub-)expression.
+    fn empty_fragment(&mut self) -> Fragment {
+        let start = self.nfa.add_state();
+        let accept = self.nfa.add_state();
+        self.nfa.states[start].push((None, accept));
+        Fragment { start, accept }
+    }
+
+    /// Kleene star: zero or more repetitions of `inner`.
+    fn star(&mut self, inner: Fragment) -> Fragment {
+        let start = self.nfa.add_state();
+        let accept = self.nfa.add_state();
+        self.nfa.states[start].push((None, inner.start));
+        self.nfa.states[start].push((None, accept));
+        self.nfa.states[inner.accept].push((None, inner.start));
+        self.nfa.states[inner.accept].push((None, accept));
+        Fragment { start, accept }
+    }
+
+    /// One or more repetitions of `inner`.
+    fn plus(&mut self, inner: Fragment) -> Fragment {
+        let start = self.nfa.add_state();
+        let accept = self.nfa.add_state();
+        self.nfa.states[start].push((None, inner.start));
+        self.nfa.states[inner.accept].push((None, inner.start));
+        self.nfa.states[inner.accept].push((None, accept));
+        Fragment { start, accept }
+    }
+
+    /// Zero or one occurrence of `inner`.
+    fn optional(&mut self, inner: Fragment) -> Fragment {
+        let start = self.nfa.add_state();
+        let accept = self.nfa.add_state();
+        self.nfa.states[start].push((None, inner.start));
+        self.nfa.states[start].push((None, accept));
+        self.nfa.states[inner.accept].push((None, accept));
+        Fragment { start, accept }
+    }
+}
+
+/// Return `true` if `c` carries special meaning in the supported grammar and so
+/// cannot stand for itself as a literal.
+fn is_metacharacter(c: char) -> bool {
+    matches!(c, '(' | ')' | '|' | '*' | '+' | '?' | '[' | ']')
+}
+
+/// Split the consuming edges' ranges into the sorted list of atomic intervals
+/// they induce: intervals break at every range start and just past every range
+/// end, so within one piece no range begins or ends and a single representative
+/// speaks for the whole interval during subset construction.
+fn atomic_intervals(ranges: &[RangeInclusive<char>]) -> Vec<RangeInclusive<char>> {
+    let mut breakpoints: Vec<u32> = Vec::with_capacity(ranges.len() * 2);
+    for range in ranges {
+        breakpoints.push(*range.start() as u32);
+        breakpoints.push(*range.end() as u32 + 1);
+    }
+    breakpoints.sort_unstable();
+    breakpoints.dedup();
+
+    let mut intervals = Vec::new();
+    for pair in breakpoints.windows(2) {
+        let (lo, hi) = (pair[0], pair[1] - 1);
+
+        // Keep the piece only where it actually lands inside some range; gaps
+        // between ranges belong to no interval and get no transition at all.
+        if !ranges
+            .iter()
+            .any(|r| (*r.start() as u32) <= lo && lo <= (*r.end() as u32))
+        {
+            continue;
+        }
+
+        if let (Some(start), Some(end)) = (char::from_u32(lo), char::from_u32(hi)) {
+            intervals.push(start..=end);
+        }
+    }
+
+    intervals
+}
+
+/// Determinise the compiled NFA into a [`DFABuilder`].
+pub(crate) fn build_from_regex(pattern: &str) -> Result<DFABuilder, DFABuilderError> {
+    let (nfa, fragment) = Nfa::compile(pattern)?;
+
+    let mut builder = DFABuilder::default();
+
+    // The alphabet is split into atomic intervals at every class boundary, so a
+    // single representative per interval drives subset construction while the
+    // DFA keeps the compact interval transitions `State` is built around. A
+    // stable (sorted) order keeps the assigned state ids deterministic.
+    let mut ranges: Vec<RangeInclusive<char>> = Vec::new();
+    for edges in &nfa.states {
+        for (symbol, _) in edges {
+            if let Some(range) = symbol {
+                ranges.push(range.clone());
+            }
+        }
+    }
+    let alphabet = atomic_intervals(&ranges);
+
+    // Map each discovered NFA state-set to a DFA state id; the ε-closure of the
+    // NFA start is always id 0.
+    let start_set = nfa.epsilon_closure(&BTreeSet::from([fragment.start]));
+    let mut ids: HashMap<BTreeSet<usize>, i32> = HashMap::new();
+    ids.insert(start_set.clone(), 0);
+
+    let mut worklist = vec![start_set];
+    let mut needs_dead_state = false;
+
+    while let Some(set) = worklist.pop() {
+        let from_id = ids[&set];
+
+        for interval in &alphabet {
+            // Characters in one atomic interval are indistinguishable to every
+            // edge, so one representative decides the whole interval's move; its
+            // ε-closure then gives the target state-set.
+            let representative = *interval.start();
+            let mut moved = BTreeSet::new();
+            for &state in &set {
+                for (symbol, target) in &nfa.states[state] {
+                    if matches!(symbol, Some(range) if range.contains(&representative)) {
+                        moved.insert(*target);
+                    }
+                }
+            }
+
+            if moved.is_empty() {
+                // No transition on this interval; it will be routed to a dead
+                // state so the transition function stays total for `build()`.
+                needs_dead_state = true;
+                continue;
+            }
+
+            let target_set = nfa.epsilon_closure(&moved);
+            let next_id = match ids.get(&target_set) {
+                Some(id) => *id,
+                None => {
+                    let id = ids.len() as i32;
+                    ids.insert(target_set.clone(), id);
+                    worklist.push(target_set);
+                    id
+                }
+            };
+
+            builder
+                .states
+                .entry(from_id)
+                .or_insert_with(State::default)
+                .add_range_transition(interval.clone(), next_id);
+        }
+
+        // Any state-set containing the NFA accept is a final state.
+        if set.contains(&fragment.accept) {
+            builder.add_final_state(from_id);
+        }
+    }
+
+    // Every discovered state-set is a DFA state, even one with no outgoing
+    // transitions (e.g. an accept-only start state over an empty alphabet).
+    for &id in ids.values() {
+        builder.states.entry(id).or_insert_with(State::default);
+    }
+
+    if needs_dead_state {
+        let dead_id = ids.len() as i32;
+
+        // The dead state loops to itself over every interval.
+        let mut dead = State::default();
+        for interval in &alphabet {
+            dead.add_range_transition(interval.clone(), dead_id);
+        }
+        builder.states.insert(dead_id, dead);
+
+        // Fill in every missing transition so the automaton is complete.
+        for id in 0..dead_id {
+            let state = builder.states.entry(id).or_insert_with(State::default);
+            for interval in &alphabet {
+                if state.transition_for(*interval.start()).is_none() {
+                    state.add_range_transition(interval.clone(), dead_id);
+                }
+            }
+        }
+    }
+
+    Ok(builder)
+}
@@ -10,38 +10,157 @@
 //! assert_eq!(state.num_transitions(), 2);
 //! ```
 
-use std::collections::HashMap;
+use core::ops::RangeInclusive;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(all(feature = "serde", not(feature = "std")))]
+use alloc::{
+    format,
+    string::{String, ToString},
+};
 
 /// State
-#[derive(Debug, PartialEq, Eq)]
+///
+/// Transitions are stored as a sorted list of non-overlapping character
+/// intervals rather than one entry per character, so a class such as `[a-z]`
+/// costs a single interval instead of one entry per codepoint.
+#[derive(Debug, Default, PartialEq, Eq)]
 pub struct State {
-    /// A map of all valid transitions from the state.
-    transitions: HashMap<char, i32>,
+    /// The transitions out of this state, kept sorted by interval start so they
+    /// can be searched with a binary search (the intervals are assumed not to
+    /// overlap).
+    transitions: Vec<(RangeInclusive<char>, i32)>,
 }
 
 impl State {
     /// Construct a new state with no initial transitions.
     pub fn new() -> Self {
-        Self { transitions: HashMap::new() }
+        Self::default()
     }
 
-    /// Add the specified transition to this state.
+    /// Add a transition on the single character `w` to this state.
     ///
     /// No validation is made that `new_state` exists until after
     /// all of the states have been added to the DFA.
     pub fn add_transition(&mut self, w: char, new_state: i32) {
-        self.transitions.insert(w, new_state);
+        self.add_range_transition(w..=w, new_state);
+    }
+
+    /// Add a transition on the character interval `range` to this state.
+    ///
+    /// The interval is inserted so the transition list stays sorted by start;
+    /// callers are responsible for not introducing overlapping intervals. An
+    /// interval sharing an existing start replaces it, matching the last-wins
+    /// behavior of the previous per-character map.
+    pub fn add_range_transition(&mut self, range: RangeInclusive<char>, new_state: i32) {
+        match self
+            .transitions
+            .binary_search_by(|(existing, _)| existing.start().cmp(range.start()))
+        {
+            Ok(pos) => self.transitions[pos] = (range, new_state),
+            Err(pos) => self.transitions.insert(pos, (range, new_state)),
+        }
     }
 
     /// Return the transition from this state for the specified input.
     pub fn transition_for(&self, w: char) -> Option<&i32> {
-        self.transitions.get(&w)
+        // Binary search for the interval containing `w`.
+        self.transitions
+            .binary_search_by(|(range, _)| {
+                if *range.end() < w {
+                    core::cmp::Ordering::Less
+                } else if *range.start() > w {
+                    core::cmp::Ordering::Greater
+                } else {
+                    core::cmp::Ordering::Equal
+                }
+            })
+            .ok()
+            .map(|idx| &self.transitions[idx].1)
     }
 
     /// Return the number of transitions from this state.
     pub fn num_transitions(&self) -> usize {
         self.transitions.len()
     }
+
+    /// Return an iterator over this state's `(interval, target state)` transitions.
+    pub fn transitions(&self) -> impl Iterator<Item = (&RangeInclusive<char>, &i32)> {
+        self.transitions.iter().map(|(range, target)| (range, target))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for State {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        // Each interval is keyed by its class string, e.g. `"a"` or `"a-z"`, so
+        // the serialized form stays a readable character-class map.
+        let mut map = serializer.serialize_map(Some(self.transitions.len()))?;
+        for (range, target) in &self.transitions {
+            map.serialize_entry(&class_key(range), target)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for State {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct StateVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for StateVisitor {
+            type Value = State;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str("a map of character classes to target states")
+            }
+
+            fn visit_map<A>(self, mut access: A) -> Result<State, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut state = State::new();
+                while let Some((key, target)) = access.next_entry::<String, i32>()? {
+                    let range = parse_class_key(&key).map_err(serde::de::Error::custom)?;
+                    state.add_range_transition(range, target);
+                }
+                Ok(state)
+            }
+        }
+
+        deserializer.deserialize_map(StateVisitor)
+    }
+}
+
+/// Render `range` as a character-class key: `"a"` for a single character or
+/// `"a-z"` for a span.
+#[cfg(feature = "serde")]
+fn class_key(range: &RangeInclusive<char>) -> String {
+    if range.start() == range.end() {
+        range.start().to_string()
+    } else {
+        format!("{}-{}", range.start(), range.end())
+    }
+}
+
+/// Parse a character-class key (`"a"` or `"a-z"`) back into an interval.
+#[cfg(feature = "serde")]
+fn parse_class_key(key: &str) -> Result<RangeInclusive<char>, &'static str> {
+    let chars: Vec<char> = key.chars().collect();
+    match chars.as_slice() {
+        [c] => Ok(*c..=*c),
+        [lo, '-', hi] => Ok(*lo..=*hi),
+        _ => Err("invalid character class key"),
+    }
 }
 
 #[cfg(test)]
@@ -63,4 +182,25 @@ mod tests {
 
         assert_eq!(initial.num_transitions(), 2);
     }
+
+    #[test]
+    fn duplicate_transition_replaces_previous() {
+        let mut state = State::new();
+        state.add_transition('a', 1);
+        state.add_transition('a', 2);
+
+        assert_eq!(state.num_transitions(), 1);
+        assert_eq!(Some(&2), state.transition_for('a'));
+    }
+
+    #[test]
+    fn range_transition_matches_whole_interval() {
+        let mut state = State::new();
+        state.add_range_transition('a'..='z', 1);
+
+        assert_eq!(Some(&1), state.transition_for('a'));
+        assert_eq!(Some(&1), state.transition_for('m'));
+        assert_eq!(Some(&1), state.transition_for('z'));
+        assert_eq!(None, state.transition_for('0'));
+    }
 }